@@ -0,0 +1,653 @@
+//! Async variant of the driver, built on `embedded-hal-async`
+//!
+//! Enabled with the `async` feature. Shares the register maps and the
+//! [`EventsIrq`](crate::EventsIrq), [`Power`](crate::Power) and
+//! [`PowerInputStatus`](crate::PowerInputStatus) types with the blocking
+//! driver, and mirrors its full API surface one-for-one (charge config, ADC,
+//! coulomb counter, programmable rail voltages, power-off and PEK timing
+//! included) so the two can be used interchangeably depending on the
+//! executor available on the target.
+
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+
+use core::ops::BitOr;
+
+use crate::{
+    AdcChannels, ChargeConfig, ChargeCurrent, ChargeEndThreshold, ChargeTargetVoltage, ChipId,
+    Error, PekConfig, PekLongPress, PekPwrokDelay, PekShutdownDelay, PekStartup, Power,
+    PowerInputStatus, PowerState, Register,
+};
+use crate::{Charge, EventsIrq};
+
+/// AXP device state
+enum State {
+    Uninitialized,
+    Initialized(ChipId),
+}
+
+/// Async AXP device representation
+pub struct Axpxx<I2C>
+where
+    I2C: I2c,
+{
+    i2c: I2C,
+    address: u8,
+    state: State,
+}
+
+impl<I2C> Axpxx<I2C>
+where
+    I2C: I2c,
+{
+    /// Create a new Axp20x device with the default slave address
+    ///
+    /// # Arguments
+    ///
+    /// - `i2c` I2C bus used to communicate with the device
+    ///
+    /// # Returns
+    ///
+    /// - [Axp20x driver](Axpxx) created
+    ///
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            address: crate::DEFAULT_AXP202_SLAVE_ADDR,
+            state: State::Uninitialized,
+        }
+    }
+
+    /// Create a new Axp20x device with a custom slave address
+    ///
+    /// # Arguments
+    ///
+    /// - `i2c` I2C bus used to communicate with the device
+    /// - `address` custom address for the device
+    ///
+    /// # Returns
+    ///
+    /// - [Axp20x driver](Axpxx) created
+    ///
+    pub fn new_with_address(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            state: State::Uninitialized,
+        }
+    }
+
+    /// Initialize the device
+    pub async fn init(&mut self) -> Result<(), Error<I2C::Error>> {
+        let chip_id = self.probe_chip().await?;
+        self.state = State::Initialized(chip_id);
+        Ok(())
+    }
+
+    async fn read_reg(&mut self, reg: Register) -> Result<u8, Error<I2C::Error>> {
+        let mut buf = [0u8; 1];
+        let read_buf = [reg.into(); 1];
+        self.i2c.write_read(self.address, &read_buf, &mut buf).await?;
+        Ok(buf[0])
+    }
+
+    async fn write_reg(&mut self, reg: Register, val: u8) -> Result<(), Error<I2C::Error>> {
+        self.i2c.write(self.address, &[reg.into(), val]).await?;
+        Ok(())
+    }
+
+    async fn probe_chip(&mut self) -> Result<ChipId, Error<I2C::Error>> {
+        let chip_id = self.read_reg(Register::IcType).await?;
+        Ok(ChipId::from(chip_id))
+    }
+
+    /// Check if power ac is present
+    ///
+    /// # Returns
+    ///
+    /// - true if power AC is present, false otherwise
+    pub async fn is_acin_present(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let power_status = self.read_reg(Register::PowerInputStatus).await?;
+        let power_status = PowerInputStatus(power_status);
+        Ok(power_status.intersects(PowerInputStatus::AcinPresence))
+    }
+
+    /// Check if power ac is usable
+    ///
+    /// # Returns
+    ///
+    /// - true if power AC is usable, false otherwise
+    pub async fn is_acin_usable(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let power_status = self.read_reg(Register::PowerInputStatus).await?;
+        let power_status = PowerInputStatus(power_status);
+        Ok(power_status.intersects(PowerInputStatus::AcinUsable))
+    }
+
+    /// Check if VBus is present
+    ///
+    /// # Returns
+    ///
+    /// - true if VBus is present, false otherwise
+    pub async fn is_vbus_present(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let power_status = self.read_reg(Register::PowerInputStatus).await?;
+        let power_status = PowerInputStatus(power_status);
+        Ok(power_status.intersects(PowerInputStatus::VbusPresence))
+    }
+
+    /// Check if VBus is usable
+    ///
+    /// # Returns
+    ///
+    /// - true if VBus is usable, false otherwise
+    pub async fn is_vbus_usable(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let power_status = self.read_reg(Register::PowerInputStatus).await?;
+        let power_status = PowerInputStatus(power_status);
+        Ok(power_status.intersects(PowerInputStatus::VbusUsable))
+    }
+
+    pub async fn is_vbus_above(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let power_status = self.read_reg(Register::PowerInputStatus).await?;
+        let power_status = PowerInputStatus(power_status);
+        Ok(power_status.intersects(PowerInputStatus::VbusAbove))
+    }
+
+    /// Check if battery is charging
+    ///
+    /// # Returns
+    ///
+    /// - true if battery is charging, false otherwise
+    pub async fn is_battery_charging(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let raw_charge1 = self.read_reg(Register::Charge1).await?;
+        Ok(Charge(raw_charge1).intersects(Charge::Charging))
+    }
+
+    /// Enable or disable the battery charger
+    pub async fn enable_charging(&mut self, enable: bool) -> Result<(), Error<I2C::Error>> {
+        let raw_charge1 = self.read_reg(Register::Charge1).await?;
+        let mut charge1 = Charge(raw_charge1);
+        if enable {
+            charge1 |= Charge::Charging;
+        } else {
+            charge1 &= !Charge::Charging;
+        }
+        self.write_reg(Register::Charge1, u8::from(charge1)).await
+    }
+
+    /// Read the current charge configuration (target voltage, charge
+    /// current and end-of-charge current threshold)
+    ///
+    /// # Returns
+    ///
+    /// - [ChargeConfig](crate::ChargeConfig) currently programmed
+    pub async fn get_charge_config(&mut self) -> Result<ChargeConfig, Error<I2C::Error>> {
+        let raw_charge1 = self.read_reg(Register::Charge1).await?;
+        Ok(ChargeConfig {
+            target_voltage: ChargeTargetVoltage::from((raw_charge1 >> 5) & 0b11),
+            end_threshold: ChargeEndThreshold::from((raw_charge1 >> 4) & 0b1),
+            current: ChargeCurrent::from(raw_charge1 & 0b1111),
+        })
+    }
+
+    /// Set the charge configuration (target voltage, charge current and
+    /// end-of-charge current threshold), preserving the charge enable bit
+    ///
+    /// # Arguments
+    ///
+    /// - `config`: [ChargeConfig](crate::ChargeConfig) to apply
+    pub async fn set_charge_config(
+        &mut self,
+        config: ChargeConfig,
+    ) -> Result<(), Error<I2C::Error>> {
+        let raw_charge1 = self.read_reg(Register::Charge1).await?;
+        let target_voltage: u8 = config.target_voltage.into();
+        let end_threshold: u8 = config.end_threshold.into();
+        let current: u8 = config.current.into();
+        let raw_charge1 = (raw_charge1 & u8::from(Charge::Charging))
+            | (target_voltage << 5)
+            | (end_threshold << 4)
+            | current;
+        self.write_reg(Register::Charge1, raw_charge1).await
+    }
+
+    pub async fn is_acin_vbus_shortcircuit(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let power_status = self.read_reg(Register::PowerInputStatus).await?;
+        let power_status = PowerInputStatus(power_status);
+        Ok(power_status.intersects(PowerInputStatus::AcinVbusShortCircuit))
+    }
+
+    pub async fn is_bootsource_acin_vbus(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let power_status = self.read_reg(Register::PowerInputStatus).await?;
+        let power_status = PowerInputStatus(power_status);
+        Ok(power_status.intersects(PowerInputStatus::BootSource))
+    }
+
+    /// Check battery percentage
+    ///
+    /// # Returns
+    ///
+    /// - Battery percentage
+    pub async fn get_battery_percentage(&mut self) -> Result<u8, Error<I2C::Error>> {
+        self.read_reg(Register::BatteryPercentage).await
+    }
+
+    /// Read the battery voltage
+    pub async fn get_battery_voltage(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let battery_high_8b = self.read_reg(Register::BatteryAverageVoltageHigh8b).await?;
+        let battery_low_4b = self.read_reg(Register::BatteryAverageVoltageLow4b).await?;
+        Ok(
+            (((battery_high_8b as u16) << 4) | (battery_low_4b & 0x0F) as u16) as f32
+                * crate::BATTERY_VOLTAGE_STEP,
+        )
+    }
+
+    /// Read a 12-bit ADC result split across an 8-bit-high/4-bit-low
+    /// register pair
+    async fn read_12b_adc(
+        &mut self,
+        high: Register,
+        low: Register,
+    ) -> Result<u16, Error<I2C::Error>> {
+        let high_8b = self.read_reg(high).await?;
+        let low_4b = self.read_reg(low).await?;
+        Ok(((high_8b as u16) << 4) | (low_4b & 0x0F) as u16)
+    }
+
+    /// Enable or disable the given ADC channels
+    ///
+    /// # Arguments
+    ///
+    /// - `channels`: [AdcChannels](crate::AdcChannels) to enable or disable
+    /// - `enable`: true to enable, false to disable
+    pub async fn enable_adc(
+        &mut self,
+        channels: AdcChannels,
+        enable: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        let low = channels.low_byte();
+        let high = channels.high_byte();
+
+        let raw_adc_en1 = self.read_reg(Register::AdcEnable1).await?;
+        let raw_adc_en1 = if enable {
+            raw_adc_en1 | low
+        } else {
+            raw_adc_en1 & !low
+        };
+        self.write_reg(Register::AdcEnable1, raw_adc_en1).await?;
+
+        let raw_adc_en2 = self.read_reg(Register::AdcEnable2).await?;
+        let raw_adc_en2 = if enable {
+            raw_adc_en2 | high
+        } else {
+            raw_adc_en2 & !high
+        };
+        self.write_reg(Register::AdcEnable2, raw_adc_en2).await?;
+        Ok(())
+    }
+
+    /// Read the battery charge current
+    pub async fn get_battery_charge_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self
+            .read_12b_adc(
+                Register::BatteryChargeCurrentHigh8b,
+                Register::BatteryChargeCurrentLow4b,
+            )
+            .await?;
+        Ok(raw as f32 * crate::BATTERY_CURRENT_STEP)
+    }
+
+    /// Read the battery discharge current
+    pub async fn get_battery_discharge_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self
+            .read_12b_adc(
+                Register::BatteryDischargeCurrentHigh8b,
+                Register::BatteryDischargeCurrentLow4b,
+            )
+            .await?;
+        Ok(raw as f32 * crate::BATTERY_CURRENT_STEP)
+    }
+
+    /// Read the ACIN input voltage
+    pub async fn get_acin_voltage(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self
+            .read_12b_adc(Register::AcinVoltageHigh8b, Register::AcinVoltageLow4b)
+            .await?;
+        Ok(raw as f32 * crate::ACIN_VOLTAGE_STEP)
+    }
+
+    /// Read the ACIN input current
+    pub async fn get_acin_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self
+            .read_12b_adc(Register::AcinCurrentHigh8b, Register::AcinCurrentLow4b)
+            .await?;
+        Ok(raw as f32 * crate::ACIN_CURRENT_STEP)
+    }
+
+    /// Read the VBUS input voltage
+    pub async fn get_vbus_voltage(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self
+            .read_12b_adc(Register::VbusVoltageHigh8b, Register::VbusVoltageLow4b)
+            .await?;
+        Ok(raw as f32 * crate::VBUS_VOLTAGE_STEP)
+    }
+
+    /// Read the VBUS input current
+    pub async fn get_vbus_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self
+            .read_12b_adc(Register::VbusCurrentHigh8b, Register::VbusCurrentLow4b)
+            .await?;
+        Ok(raw as f32 * crate::VBUS_CURRENT_STEP)
+    }
+
+    /// Read the internal die temperature
+    pub async fn get_internal_temperature(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self
+            .read_12b_adc(
+                Register::InternalTemperatureHigh8b,
+                Register::InternalTemperatureLow4b,
+            )
+            .await?;
+        Ok(raw as f32 * crate::INTERNAL_TEMPERATURE_STEP + crate::INTERNAL_TEMPERATURE_OFFSET)
+    }
+
+    /// Read a big-endian 32-bit counter split across 4 consecutive registers
+    async fn read_32b(&mut self, bytes: [Register; 4]) -> Result<u32, Error<I2C::Error>> {
+        let mut value: u32 = 0;
+        for reg in bytes {
+            value = (value << 8) | self.read_reg(reg).await? as u32;
+        }
+        Ok(value)
+    }
+
+    /// Enable or disable the coulomb counter
+    pub async fn enable_coulomb_counter(&mut self, enable: bool) -> Result<(), Error<I2C::Error>> {
+        let raw_ctrl = self.read_reg(Register::CoulombCounterCtrl).await?;
+        let raw_ctrl = if enable {
+            raw_ctrl | (1 << 7)
+        } else {
+            raw_ctrl & !(1 << 7)
+        };
+        self.write_reg(Register::CoulombCounterCtrl, raw_ctrl).await
+    }
+
+    /// Pause or resume the coulomb counter without clearing its value
+    pub async fn suspend_coulomb_counter(
+        &mut self,
+        suspend: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        let raw_ctrl = self.read_reg(Register::CoulombCounterCtrl).await?;
+        let raw_ctrl = if suspend {
+            raw_ctrl | (1 << 6)
+        } else {
+            raw_ctrl & !(1 << 6)
+        };
+        self.write_reg(Register::CoulombCounterCtrl, raw_ctrl).await
+    }
+
+    /// Clear the coulomb counter back to zero
+    pub async fn clear_coulomb_counter(&mut self) -> Result<(), Error<I2C::Error>> {
+        let raw_ctrl = self.read_reg(Register::CoulombCounterCtrl).await?;
+        self.write_reg(Register::CoulombCounterCtrl, raw_ctrl | (1 << 5))
+            .await
+    }
+
+    /// Read the raw 32-bit charge coulomb counter
+    pub async fn get_coulomb_charge(&mut self) -> Result<u32, Error<I2C::Error>> {
+        self.read_32b([
+            Register::CoulombChargeByte0,
+            Register::CoulombChargeByte1,
+            Register::CoulombChargeByte2,
+            Register::CoulombChargeByte3,
+        ])
+        .await
+    }
+
+    /// Read the raw 32-bit discharge coulomb counter
+    pub async fn get_coulomb_discharge(&mut self) -> Result<u32, Error<I2C::Error>> {
+        self.read_32b([
+            Register::CoulombDischargeByte0,
+            Register::CoulombDischargeByte1,
+            Register::CoulombDischargeByte2,
+            Register::CoulombDischargeByte3,
+        ])
+        .await
+    }
+
+    /// Compute the net battery charge from the coulomb counters
+    ///
+    /// # Arguments
+    ///
+    /// - `adc_rate`: ADC sample rate currently configured, in Hz
+    ///
+    /// # Returns
+    ///
+    /// - Net battery charge in mAh, following the datasheet formula
+    pub async fn get_battery_charge_mah(
+        &mut self,
+        adc_rate: f32,
+    ) -> Result<f32, Error<I2C::Error>> {
+        let charge = self.get_coulomb_charge().await?;
+        let discharge = self.get_coulomb_discharge().await?;
+        Ok(65536.0 * 0.5 * (charge as f32 - discharge as f32) / (3600.0 * adc_rate))
+    }
+
+    /// Toggle the given interrupt sources on or off
+    pub async fn toggle_irq(
+        &mut self,
+        irqs: EventsIrq,
+        enable: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        if irqs.is_int1() {
+            let irq1 = self.read_reg(Register::EnabledIrq1).await?;
+            let irq1 = EventsIrq::from_int1_u8(irq1);
+            let irqs = irqs.toggle(irq1, enable);
+            self.write_reg(Register::EnabledIrq1, irqs.into_int1_u8())
+                .await?;
+        }
+        if irqs.is_int2() {
+            let irq2 = self.read_reg(Register::EnabledIrq2).await?;
+            let irq2 = EventsIrq::from_int2_u8(irq2).bitor(irqs);
+            let irqs = irqs.toggle(irq2, enable);
+            self.write_reg(Register::EnabledIrq2, irqs.into_int2_u8())
+                .await?;
+        }
+        if irqs.is_int3() {
+            let irq3 = self.read_reg(Register::EnabledIrq3).await?;
+            let irq3 = EventsIrq::from_int3_u8(irq3).bitor(irqs);
+            let irqs = irqs.toggle(irq3, enable);
+            self.write_reg(Register::EnabledIrq3, irqs.into_int3_u8())
+                .await?;
+        }
+        if irqs.is_int4() {
+            let irq4 = self.read_reg(Register::EnabledIrq4).await?;
+            let irq4 = EventsIrq::from_int4_u8(irq4).bitor(irqs);
+            let irqs = irqs.toggle(irq4, enable);
+            self.write_reg(Register::EnabledIrq4, irqs.into_int4_u8())
+                .await?;
+        }
+        if irqs.is_int5() {
+            let irq5 = self.read_reg(Register::EnabledIrq5).await?;
+            let irq5 = EventsIrq::from_int5_u8(irq5).bitor(irqs);
+            let irqs = irqs.toggle(irq5, enable);
+            self.write_reg(Register::EnabledIrq5, irqs.into_int5_u8())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Clear every pending interrupt status bit
+    pub async fn clear_irq(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.write_reg(Register::StatusIrq1, 0xFF).await?;
+        self.write_reg(Register::StatusIrq2, 0xFF).await?;
+        self.write_reg(Register::StatusIrq3, 0xFF).await?;
+        self.write_reg(Register::StatusIrq4, 0xFF).await?;
+        self.write_reg(Register::StatusIrq5, 0xFF).await?;
+        Ok(())
+    }
+
+    /// Read and clear the pending interrupt sources
+    pub async fn read_irq(&mut self) -> Result<EventsIrq, Error<I2C::Error>> {
+        let irq1 = self.read_reg(Register::StatusIrq1).await?;
+        let irq2 = self.read_reg(Register::StatusIrq2).await?;
+        let irq3 = self.read_reg(Register::StatusIrq3).await?;
+        let irq4 = self.read_reg(Register::StatusIrq4).await?;
+        let irq5 = self.read_reg(Register::StatusIrq5).await?;
+        self.clear_irq().await?;
+        Ok(EventsIrq::from_int1_u8(irq1)
+            .bitor(EventsIrq::from_int2_u8(irq2))
+            .bitor(EventsIrq::from_int3_u8(irq3))
+            .bitor(EventsIrq::from_int4_u8(irq4))
+            .bitor(EventsIrq::from_int5_u8(irq5)))
+    }
+
+    /// Set power output for modules
+    ///
+    /// # Arguments
+    ///
+    /// - `channel`: [Power](crate::Power) channel to manage
+    /// - `state`: [PowerState](crate::PowerState) to set (On or Off)
+    /// - `delay`: async [delay source](embedded_hal_async::delay::DelayNs) to use
+    pub async fn set_power_output(
+        &mut self,
+        channel: Power,
+        state: PowerState,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<I2C::Error>> {
+        match self.state {
+            State::Uninitialized => Err(Error::Uninitialized),
+            State::Initialized(chip_id) => {
+                // Before setting, the output cannot be all turned off
+                let mut data: u8;
+                loop {
+                    data = self.read_reg(Register::Ldo234Dc23Ctl).await?;
+                    delay.delay_ms(10).await;
+                    if data != 0 {
+                        break;
+                    }
+                }
+
+                let mut data = Power::from(data);
+
+                match state {
+                    PowerState::On => {
+                        data |= channel;
+                    }
+                    PowerState::Off => {
+                        data &= !channel;
+                    }
+                };
+
+                if chip_id == ChipId::Axp202 {
+                    data |= Power::DcDc3.into();
+                }
+                self.write_reg(Register::Ldo234Dc23Ctl, u8::from(data))
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Set the LDO2 output voltage
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 1800-3300 mV in 100 mV steps
+    pub async fn set_ldo2_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = crate::linear_mv_to_code(millivolts, 1800, 3300, 100);
+        let raw_ldo24 = self.read_reg(Register::Ldo24OutVol).await?;
+        self.write_reg(Register::Ldo24OutVol, (raw_ldo24 & 0x0F) | (code << 4))
+            .await
+    }
+
+    /// Set the LDO3 output voltage
+    ///
+    /// LDO3 has its own dedicated register (`Ldo3OutVol`, 0x29) rather than
+    /// sharing a nibble of `Ldo24OutVol`: bit 7 selects DC-DC2 tracking
+    /// mode and bits 6-0 hold a 7-bit, 25 mV/step code.
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 700-3300 mV in 25 mV steps
+    pub async fn set_ldo3_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = crate::linear_mv_to_code(millivolts, 700, 3300, 25);
+        let raw_ldo3 = self.read_reg(Register::Ldo3OutVol).await?;
+        self.write_reg(Register::Ldo3OutVol, (raw_ldo3 & 0x80) | (code & 0x7F))
+            .await
+    }
+
+    /// Set the LDO4 output voltage
+    ///
+    /// LDO4 shares `Ldo24OutVol` (0x28) with LDO2: LDO2 is the high nibble,
+    /// LDO4 the low nibble. This differs from the 0x27 address used for
+    /// DC-DC3 (see [Register::Dcdc3OutVol]) so the two rails don't alias.
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, snapped to the closest entry in the
+    ///   LDO4 voltage table (1800-3700 mV)
+    pub async fn set_ldo4_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = crate::ldo4_mv_to_code(millivolts);
+        let raw_ldo24 = self.read_reg(Register::Ldo24OutVol).await?;
+        self.write_reg(Register::Ldo24OutVol, (raw_ldo24 & 0xF0) | (code & 0x0F))
+            .await
+    }
+
+    /// Set the DC-DC2 output voltage
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 700-2275 mV in 25 mV steps
+    pub async fn set_dcdc2_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = crate::linear_mv_to_code(millivolts, 700, 2275, 25);
+        let raw_dcdc2 = self.read_reg(Register::Dcdc2OutVol).await?;
+        self.write_reg(Register::Dcdc2OutVol, (raw_dcdc2 & 0xC0) | (code & 0x3F))
+            .await
+    }
+
+    /// Set the DC-DC3 output voltage
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 700-3500 mV in 25 mV steps
+    pub async fn set_dcdc3_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = crate::linear_mv_to_code(millivolts, 700, 3500, 25);
+        let raw_dcdc3 = self.read_reg(Register::Dcdc3OutVol).await?;
+        self.write_reg(Register::Dcdc3OutVol, (raw_dcdc3 & 0x80) | (code & 0x7F))
+            .await
+    }
+
+    /// Cut all power rails by setting the shutdown bit
+    pub async fn power_off(&mut self) -> Result<(), Error<I2C::Error>> {
+        let raw_shutdown = self.read_reg(Register::PowerOffCtl).await?;
+        self.write_reg(Register::PowerOffCtl, raw_shutdown | (1 << 7))
+            .await
+    }
+
+    /// Read the current power-key (PEK) timing configuration
+    pub async fn get_pek_config(&mut self) -> Result<PekConfig, Error<I2C::Error>> {
+        let raw_pek = self.read_reg(Register::PekTiming).await?;
+        Ok(PekConfig {
+            startup: PekStartup::from((raw_pek >> 6) & 0b11),
+            long_press: PekLongPress::from((raw_pek >> 4) & 0b11),
+            pwrok_delay: PekPwrokDelay::from((raw_pek >> 2) & 0b1),
+            shutdown_delay: PekShutdownDelay::from(raw_pek & 0b11),
+        })
+    }
+
+    /// Set the power-key (PEK) timing configuration
+    ///
+    /// # Arguments
+    ///
+    /// - `config`: [PekConfig](crate::PekConfig) to apply
+    pub async fn set_pek_config(&mut self, config: PekConfig) -> Result<(), Error<I2C::Error>> {
+        let raw_pek = self.read_reg(Register::PekTiming).await?;
+        let startup: u8 = config.startup.into();
+        let long_press: u8 = config.long_press.into();
+        let pwrok_delay: u8 = config.pwrok_delay.into();
+        let shutdown_delay: u8 = config.shutdown_delay.into();
+        let raw_pek = (raw_pek & 0b0000_1000)
+            | (startup << 6)
+            | (long_press << 4)
+            | (pwrok_delay << 2)
+            | shutdown_delay;
+        self.write_reg(Register::PekTiming, raw_pek).await
+    }
+}
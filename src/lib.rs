@@ -1,5 +1,17 @@
 #![no_std]
 
+// This tree ships as a source snapshot without a `Cargo.toml`, so the
+// `async` feature below has nothing to declare it. Wiring it up for real
+// requires, in the manifest:
+//
+//   [dependencies]
+//   embedded-hal-async = { version = "1", optional = true }
+//
+//   [features]
+//   async = ["dep:embedded-hal-async"]
+#[cfg(feature = "async")]
+pub mod asynch;
+
 use embedded_hal::{delay::DelayNs, i2c::I2c};
 
 use core::{
@@ -10,8 +22,15 @@ use core::{
 use bitmask_enum::bitmask;
 use num_enum::{FromPrimitive, IntoPrimitive};
 
-const DEFAULT_AXP202_SLAVE_ADDR: u8 = 0x35;
-const BATTERY_VOLTAGE_STEP: f32 = 1.1;
+pub(crate) const DEFAULT_AXP202_SLAVE_ADDR: u8 = 0x35;
+pub(crate) const BATTERY_VOLTAGE_STEP: f32 = 1.1;
+const BATTERY_CURRENT_STEP: f32 = 0.5;
+const ACIN_VOLTAGE_STEP: f32 = 1.7;
+const ACIN_CURRENT_STEP: f32 = 0.625;
+const VBUS_VOLTAGE_STEP: f32 = 1.7;
+const VBUS_CURRENT_STEP: f32 = 0.375;
+const INTERNAL_TEMPERATURE_STEP: f32 = 0.1;
+const INTERNAL_TEMPERATURE_OFFSET: f32 = -144.7;
 
 /// Power state for the different modules
 #[derive(Debug)]
@@ -52,11 +71,190 @@ pub enum Charge {
     Charging = Self(1 << 7),
 }
 
+/// Charge target (termination) voltage, bits 6-5 of `Charge1`
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+pub enum ChargeTargetVoltage {
+    #[default]
+    V4_10 = 0b00,
+    V4_15 = 0b01,
+    V4_20 = 0b10,
+    V4_36 = 0b11,
+}
+
+/// End-of-charge current threshold, bit 4 of `Charge1`, as a percentage of
+/// the constant-current charge current
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+pub enum ChargeEndThreshold {
+    #[default]
+    Pct10 = 0,
+    Pct15 = 1,
+}
+
+/// Constant-current charge current, bits 3-0 of `Charge1`, in ~100 mA steps
+/// starting at 300 mA
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+pub enum ChargeCurrent {
+    #[default]
+    Ma300 = 0b0000,
+    Ma400 = 0b0001,
+    Ma500 = 0b0010,
+    Ma600 = 0b0011,
+    Ma700 = 0b0100,
+    Ma800 = 0b0101,
+    Ma900 = 0b0110,
+    Ma1000 = 0b0111,
+    Ma1100 = 0b1000,
+    Ma1200 = 0b1001,
+    Ma1300 = 0b1010,
+    Ma1400 = 0b1011,
+    Ma1500 = 0b1100,
+    Ma1600 = 0b1101,
+    Ma1700 = 0b1110,
+    Ma1800 = 0b1111,
+}
+
+/// Battery charge configuration, as programmed in the `Charge1` register
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChargeConfig {
+    pub target_voltage: ChargeTargetVoltage,
+    pub current: ChargeCurrent,
+    pub end_threshold: ChargeEndThreshold,
+}
+
+/// ADC channels, spanning the `AdcEnable1` (low byte) and `AdcEnable2`
+/// (high byte) registers
+#[bitmask(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdcChannels {
+    VbusCurrent = Self(1 << 2),
+    VbusVoltage = Self(1 << 3),
+    AcinCurrent = Self(1 << 4),
+    AcinVoltage = Self(1 << 5),
+    BatteryCurrent = Self(1 << 6),
+    BatteryVoltage = Self(1 << 7),
+
+    InternalTemperature = Self(1 << 15),
+}
+
+/// Power-on press duration required to start up from a key press, bits
+/// 7-6 of `PekTiming`
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+pub enum PekStartup {
+    #[default]
+    Ms128 = 0b00,
+    Ms512 = 0b01,
+    S1 = 0b10,
+    S2 = 0b11,
+}
+
+/// Power key long-press duration, bits 5-4 of `PekTiming`
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+pub enum PekLongPress {
+    #[default]
+    S1 = 0b00,
+    S1Dot5 = 0b01,
+    S2 = 0b10,
+    S2Dot5 = 0b11,
+}
+
+/// Delay before the PWROK signal is asserted after power-up, bit 2 of
+/// `PekTiming`
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+pub enum PekPwrokDelay {
+    #[default]
+    Ms32 = 0,
+    Ms64 = 1,
+}
+
+/// Automatic shutdown duration once a long key press is detected, bits
+/// 1-0 of `PekTiming`
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
+pub enum PekShutdownDelay {
+    #[default]
+    S4 = 0b00,
+    S6 = 0b01,
+    S8 = 0b10,
+    S10 = 0b11,
+}
+
+/// Power-key (PEK) timing configuration, as programmed in `PekTiming`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PekConfig {
+    pub startup: PekStartup,
+    pub long_press: PekLongPress,
+    pub pwrok_delay: PekPwrokDelay,
+    pub shutdown_delay: PekShutdownDelay,
+}
+
+impl AdcChannels {
+    fn low_byte(&self) -> u8 {
+        (u16::from(*self) & 0x00FF) as u8
+    }
+
+    fn high_byte(&self) -> u8 {
+        ((u16::from(*self) & 0xFF00) >> 8) as u8
+    }
+}
+
 /// Interrupt sources
+///
+/// Bit positions follow the AXP202X_Library (lewisxhe) IRQ enumeration,
+/// which is also what the existing `PowerKeyShortPress` bit was taken from.
 #[bitmask(u64)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventsIrq {
+    // Int1 (0x40/0x48)
+    VbusLowVoltage = Self(1 << 1),
+    VbusRemoved = Self(1 << 2),
+    VbusInserted = Self(1 << 3),
+    VbusOvervoltage = Self(1 << 4),
+    AcinRemoved = Self(1 << 5),
+    AcinInserted = Self(1 << 6),
+    AcinOvervoltage = Self(1 << 7),
+
+    // Int2 (0x41/0x49)
+    BatteryUnderTemperature = Self(1 << 8),
+    BatteryOverTemperature = Self(1 << 9),
+    ChargingFinished = Self(1 << 10),
+    ChargingStarted = Self(1 << 11),
+    BatteryActivationExited = Self(1 << 12),
+    BatteryActivationEntered = Self(1 << 13),
+    BatteryRemoved = Self(1 << 14),
+    BatteryInserted = Self(1 << 15),
+
+    // Int3 (0x42/0x4A)
+    PowerKeyLongPress = Self(1 << 16),
     PowerKeyShortPress = Self(1 << 17),
+    Ldo3LowVoltage = Self(1 << 18),
+    Dc3LowVoltage = Self(1 << 19),
+    Dc2LowVoltage = Self(1 << 20),
+    ChargeCurrentLow = Self(1 << 21),
+    ChipOverTemperature = Self(1 << 22),
+    ApsLowVoltage = Self(1 << 23),
+
+    // Int4 (0x43/0x4B)
+    VbusSessionEnd = Self(1 << 24),
+    VbusSessionAb = Self(1 << 25),
+    VbusInvalid = Self(1 << 26),
+    VbusValid = Self(1 << 27),
+    NOeOff = Self(1 << 28),
+    NOeOn = Self(1 << 29),
+
+    // Int5 (0x45/0x4C)
+    TimerTimeout = Self(1 << 32),
+    PekRisingEdge = Self(1 << 33),
+    PekFallingEdge = Self(1 << 34),
+    Gpio3Input = Self(1 << 35),
+    Gpio2Input = Self(1 << 36),
+    Gpio1Input = Self(1 << 37),
+    Gpio0Input = Self(1 << 38),
 
     Int1 = Self(0xFF),
     Int2 = Self(0xFF00),
@@ -151,18 +349,85 @@ impl EventsIrq {
             self.bitand(!current_mask)
         }
     }
+
+    /// All individually-named interrupt events, excluding the per-bank
+    /// aggregate masks (`Int1`..`Int5`)
+    const ALL_EVENTS: &'static [EventsIrq] = &[
+        Self::VbusLowVoltage,
+        Self::VbusRemoved,
+        Self::VbusInserted,
+        Self::VbusOvervoltage,
+        Self::AcinRemoved,
+        Self::AcinInserted,
+        Self::AcinOvervoltage,
+        Self::BatteryUnderTemperature,
+        Self::BatteryOverTemperature,
+        Self::ChargingFinished,
+        Self::ChargingStarted,
+        Self::BatteryActivationExited,
+        Self::BatteryActivationEntered,
+        Self::BatteryRemoved,
+        Self::BatteryInserted,
+        Self::PowerKeyLongPress,
+        Self::PowerKeyShortPress,
+        Self::Ldo3LowVoltage,
+        Self::Dc3LowVoltage,
+        Self::Dc2LowVoltage,
+        Self::ChargeCurrentLow,
+        Self::ChipOverTemperature,
+        Self::ApsLowVoltage,
+        Self::VbusSessionEnd,
+        Self::VbusSessionAb,
+        Self::VbusInvalid,
+        Self::VbusValid,
+        Self::NOeOff,
+        Self::NOeOn,
+        Self::TimerTimeout,
+        Self::PekRisingEdge,
+        Self::PekFallingEdge,
+        Self::Gpio3Input,
+        Self::Gpio2Input,
+        Self::Gpio1Input,
+        Self::Gpio0Input,
+    ];
+
+    /// Iterate over the individual named events set in this value, so a
+    /// consumer can `match` on each one instead of masking manually
+    pub fn events(&self) -> impl Iterator<Item = EventsIrq> + '_ {
+        let value = *self;
+        Self::ALL_EVENTS
+            .iter()
+            .copied()
+            .filter(move |event| value.intersects(*event))
+    }
 }
 
 /// AXP20x registers
 #[allow(dead_code)]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, IntoPrimitive)]
-enum Register {
+pub(crate) enum Register {
     PowerInputStatus = 0x00,
     PowerWorkingModeChargeStatus = 0x01,
     IcType = 0x03,
     Ldo234Dc23Ctl = 0x12,
+    Dcdc2OutVol = 0x23,
+    Dcdc3OutVol = 0x27,
+    Ldo24OutVol = 0x28,
+    Ldo3OutVol = 0x29,
+    PowerOffCtl = 0x32,
     Charge1 = 0x33,
+    PekTiming = 0x36,
+    AcinVoltageHigh8b = 0x56,
+    AcinVoltageLow4b = 0x57,
+    AcinCurrentHigh8b = 0x58,
+    AcinCurrentLow4b = 0x59,
+    VbusVoltageHigh8b = 0x5A,
+    VbusVoltageLow4b = 0x5B,
+    VbusCurrentHigh8b = 0x5C,
+    VbusCurrentLow4b = 0x5D,
+    InternalTemperatureHigh8b = 0x5E,
+    InternalTemperatureLow4b = 0x5F,
     EnabledIrq1 = 0x40,
     EnabledIrq2 = 0x41,
     EnabledIrq3 = 0x42,
@@ -175,13 +440,29 @@ enum Register {
     StatusIrq5 = 0x4C,
     BatteryAverageVoltageHigh8b = 0x78,
     BatteryAverageVoltageLow4b = 0x79,
+    BatteryChargeCurrentHigh8b = 0x7A,
+    BatteryChargeCurrentLow4b = 0x7B,
+    BatteryDischargeCurrentHigh8b = 0x7C,
+    BatteryDischargeCurrentLow4b = 0x7D,
+    AdcEnable1 = 0x82,
+    AdcEnable2 = 0x83,
+    AdcSampleRate = 0x84,
+    CoulombChargeByte0 = 0xB0,
+    CoulombChargeByte1 = 0xB1,
+    CoulombChargeByte2 = 0xB2,
+    CoulombChargeByte3 = 0xB3,
+    CoulombDischargeByte0 = 0xB4,
+    CoulombDischargeByte1 = 0xB5,
+    CoulombDischargeByte2 = 0xB6,
+    CoulombDischargeByte3 = 0xB7,
+    CoulombCounterCtrl = 0xB8,
     BatteryPercentage = 0xB9,
 }
 
 /// AXP20x chip ids
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, FromPrimitive)]
-enum ChipId {
+pub(crate) enum ChipId {
     #[default]
     Unknown = 0x00,
     Axp202 = 0x41,
@@ -229,6 +510,33 @@ enum State {
     Initialized(ChipId),
 }
 
+/// Translate a millivolt value into a linear register code, clamping to
+/// the rail's supported range
+///
+/// Shared by the blocking and async drivers, since both encode rail
+/// voltages the same way.
+pub(crate) fn linear_mv_to_code(millivolts: u16, min_mv: u16, max_mv: u16, step_mv: u16) -> u8 {
+    let clamped = millivolts.clamp(min_mv, max_mv);
+    ((clamped - min_mv) / step_mv) as u8
+}
+
+/// Translate a millivolt value into an LDO4 register code, picking the
+/// closest entry in its non-linear voltage table (AXP202 datasheet,
+/// LDO4 output voltage setting table)
+pub(crate) fn ldo4_mv_to_code(millivolts: u16) -> u8 {
+    const TABLE: [u16; 16] = [
+        1800, 2000, 2200, 2400, 2500, 2600, 2700, 2800, 3000, 3100, 3200, 3300, 3400, 3500, 3600,
+        3700,
+    ];
+    let clamped = millivolts.clamp(TABLE[0], TABLE[TABLE.len() - 1]);
+    TABLE
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &v)| v.abs_diff(clamped))
+        .map(|(i, _)| i as u8)
+        .unwrap_or(0)
+}
+
 impl<I2C> Axpxx<I2C>
 where
     I2C: I2c,
@@ -354,6 +662,51 @@ where
         Ok(Charge(raw_charge1).intersects(Charge::Charging))
     }
 
+    /// Enable or disable the battery charger
+    pub fn enable_charging(&mut self, enable: bool) -> Result<(), Error<I2C::Error>> {
+        let raw_charge1 = self.read_reg(Register::Charge1)?;
+        let mut charge1 = Charge(raw_charge1);
+        if enable {
+            charge1 |= Charge::Charging;
+        } else {
+            charge1 &= !Charge::Charging;
+        }
+        self.write_reg(Register::Charge1, u8::from(charge1))
+    }
+
+    /// Read the current charge configuration (target voltage, charge
+    /// current and end-of-charge current threshold)
+    ///
+    /// # Returns
+    ///
+    /// - [ChargeConfig](ChargeConfig) currently programmed
+    pub fn get_charge_config(&mut self) -> Result<ChargeConfig, Error<I2C::Error>> {
+        let raw_charge1 = self.read_reg(Register::Charge1)?;
+        Ok(ChargeConfig {
+            target_voltage: ChargeTargetVoltage::from((raw_charge1 >> 5) & 0b11),
+            end_threshold: ChargeEndThreshold::from((raw_charge1 >> 4) & 0b1),
+            current: ChargeCurrent::from(raw_charge1 & 0b1111),
+        })
+    }
+
+    /// Set the charge configuration (target voltage, charge current and
+    /// end-of-charge current threshold), preserving the charge enable bit
+    ///
+    /// # Arguments
+    ///
+    /// - `config`: [ChargeConfig](ChargeConfig) to apply
+    pub fn set_charge_config(&mut self, config: ChargeConfig) -> Result<(), Error<I2C::Error>> {
+        let raw_charge1 = self.read_reg(Register::Charge1)?;
+        let target_voltage: u8 = config.target_voltage.into();
+        let end_threshold: u8 = config.end_threshold.into();
+        let current: u8 = config.current.into();
+        let raw_charge1 = (raw_charge1 & u8::from(Charge::Charging))
+            | (target_voltage << 5)
+            | (end_threshold << 4)
+            | current;
+        self.write_reg(Register::Charge1, raw_charge1)
+    }
+
     pub fn is_acin_vbus_shortcircuit(&mut self) -> Result<bool, Error<I2C::Error>> {
         let power_status = self.read_reg(Register::PowerInputStatus)?;
         let power_status = PowerInputStatus(power_status);
@@ -384,6 +737,169 @@ where
         )
     }
 
+    /// Read a 12-bit ADC result split across an 8-bit-high/4-bit-low
+    /// register pair
+    fn read_12b_adc(&mut self, high: Register, low: Register) -> Result<u16, Error<I2C::Error>> {
+        let high_8b = self.read_reg(high)?;
+        let low_4b = self.read_reg(low)?;
+        Ok(((high_8b as u16) << 4) | (low_4b & 0x0F) as u16)
+    }
+
+    /// Enable or disable the given ADC channels
+    ///
+    /// # Arguments
+    ///
+    /// - `channels`: [AdcChannels](AdcChannels) to enable or disable
+    /// - `enable`: true to enable, false to disable
+    pub fn enable_adc(
+        &mut self,
+        channels: AdcChannels,
+        enable: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        let low = channels.low_byte();
+        let high = channels.high_byte();
+
+        let raw_adc_en1 = self.read_reg(Register::AdcEnable1)?;
+        let raw_adc_en1 = if enable {
+            raw_adc_en1 | low
+        } else {
+            raw_adc_en1 & !low
+        };
+        self.write_reg(Register::AdcEnable1, raw_adc_en1)?;
+
+        let raw_adc_en2 = self.read_reg(Register::AdcEnable2)?;
+        let raw_adc_en2 = if enable {
+            raw_adc_en2 | high
+        } else {
+            raw_adc_en2 & !high
+        };
+        self.write_reg(Register::AdcEnable2, raw_adc_en2)?;
+        Ok(())
+    }
+
+    /// Read the battery charge current
+    pub fn get_battery_charge_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self.read_12b_adc(
+            Register::BatteryChargeCurrentHigh8b,
+            Register::BatteryChargeCurrentLow4b,
+        )?;
+        Ok(raw as f32 * BATTERY_CURRENT_STEP)
+    }
+
+    /// Read the battery discharge current
+    pub fn get_battery_discharge_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self.read_12b_adc(
+            Register::BatteryDischargeCurrentHigh8b,
+            Register::BatteryDischargeCurrentLow4b,
+        )?;
+        Ok(raw as f32 * BATTERY_CURRENT_STEP)
+    }
+
+    /// Read the ACIN input voltage
+    pub fn get_acin_voltage(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self.read_12b_adc(Register::AcinVoltageHigh8b, Register::AcinVoltageLow4b)?;
+        Ok(raw as f32 * ACIN_VOLTAGE_STEP)
+    }
+
+    /// Read the ACIN input current
+    pub fn get_acin_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self.read_12b_adc(Register::AcinCurrentHigh8b, Register::AcinCurrentLow4b)?;
+        Ok(raw as f32 * ACIN_CURRENT_STEP)
+    }
+
+    /// Read the VBUS input voltage
+    pub fn get_vbus_voltage(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self.read_12b_adc(Register::VbusVoltageHigh8b, Register::VbusVoltageLow4b)?;
+        Ok(raw as f32 * VBUS_VOLTAGE_STEP)
+    }
+
+    /// Read the VBUS input current
+    pub fn get_vbus_current(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self.read_12b_adc(Register::VbusCurrentHigh8b, Register::VbusCurrentLow4b)?;
+        Ok(raw as f32 * VBUS_CURRENT_STEP)
+    }
+
+    /// Read the internal die temperature
+    pub fn get_internal_temperature(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let raw = self.read_12b_adc(
+            Register::InternalTemperatureHigh8b,
+            Register::InternalTemperatureLow4b,
+        )?;
+        Ok(raw as f32 * INTERNAL_TEMPERATURE_STEP + INTERNAL_TEMPERATURE_OFFSET)
+    }
+
+    /// Read a big-endian 32-bit counter split across 4 consecutive registers
+    fn read_32b(&mut self, bytes: [Register; 4]) -> Result<u32, Error<I2C::Error>> {
+        let mut value: u32 = 0;
+        for reg in bytes {
+            value = (value << 8) | self.read_reg(reg)? as u32;
+        }
+        Ok(value)
+    }
+
+    /// Enable or disable the coulomb counter
+    pub fn enable_coulomb_counter(&mut self, enable: bool) -> Result<(), Error<I2C::Error>> {
+        let raw_ctrl = self.read_reg(Register::CoulombCounterCtrl)?;
+        let raw_ctrl = if enable {
+            raw_ctrl | (1 << 7)
+        } else {
+            raw_ctrl & !(1 << 7)
+        };
+        self.write_reg(Register::CoulombCounterCtrl, raw_ctrl)
+    }
+
+    /// Pause or resume the coulomb counter without clearing its value
+    pub fn suspend_coulomb_counter(&mut self, suspend: bool) -> Result<(), Error<I2C::Error>> {
+        let raw_ctrl = self.read_reg(Register::CoulombCounterCtrl)?;
+        let raw_ctrl = if suspend {
+            raw_ctrl | (1 << 6)
+        } else {
+            raw_ctrl & !(1 << 6)
+        };
+        self.write_reg(Register::CoulombCounterCtrl, raw_ctrl)
+    }
+
+    /// Clear the coulomb counter back to zero
+    pub fn clear_coulomb_counter(&mut self) -> Result<(), Error<I2C::Error>> {
+        let raw_ctrl = self.read_reg(Register::CoulombCounterCtrl)?;
+        self.write_reg(Register::CoulombCounterCtrl, raw_ctrl | (1 << 5))
+    }
+
+    /// Read the raw 32-bit charge coulomb counter
+    pub fn get_coulomb_charge(&mut self) -> Result<u32, Error<I2C::Error>> {
+        self.read_32b([
+            Register::CoulombChargeByte0,
+            Register::CoulombChargeByte1,
+            Register::CoulombChargeByte2,
+            Register::CoulombChargeByte3,
+        ])
+    }
+
+    /// Read the raw 32-bit discharge coulomb counter
+    pub fn get_coulomb_discharge(&mut self) -> Result<u32, Error<I2C::Error>> {
+        self.read_32b([
+            Register::CoulombDischargeByte0,
+            Register::CoulombDischargeByte1,
+            Register::CoulombDischargeByte2,
+            Register::CoulombDischargeByte3,
+        ])
+    }
+
+    /// Compute the net battery charge from the coulomb counters
+    ///
+    /// # Arguments
+    ///
+    /// - `adc_rate`: ADC sample rate currently configured, in Hz
+    ///
+    /// # Returns
+    ///
+    /// - Net battery charge in mAh, following the datasheet formula
+    pub fn get_battery_charge_mah(&mut self, adc_rate: f32) -> Result<f32, Error<I2C::Error>> {
+        let charge = self.get_coulomb_charge()?;
+        let discharge = self.get_coulomb_discharge()?;
+        Ok(65536.0 * 0.5 * (charge as f32 - discharge as f32) / (3600.0 * adc_rate))
+    }
+
     pub fn toggle_irq(&mut self, irqs: EventsIrq, enable: bool) -> Result<(), Error<I2C::Error>> {
         if irqs.is_int1() {
             let irq1 = self.read_reg(Register::EnabledIrq1)?;
@@ -486,4 +1002,104 @@ where
             }
         }
     }
+
+    /// Set the LDO2 output voltage
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 1800-3300 mV in 100 mV steps
+    pub fn set_ldo2_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = linear_mv_to_code(millivolts, 1800, 3300, 100);
+        let raw_ldo24 = self.read_reg(Register::Ldo24OutVol)?;
+        self.write_reg(Register::Ldo24OutVol, (raw_ldo24 & 0x0F) | (code << 4))
+    }
+
+    /// Set the LDO3 output voltage
+    ///
+    /// LDO3 has its own dedicated register (`Ldo3OutVol`, 0x29) rather than
+    /// sharing a nibble of `Ldo24OutVol`: bit 7 selects DC-DC2 tracking
+    /// mode and bits 6-0 hold a 7-bit, 25 mV/step code.
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 700-3300 mV in 25 mV steps
+    pub fn set_ldo3_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = linear_mv_to_code(millivolts, 700, 3300, 25);
+        let raw_ldo3 = self.read_reg(Register::Ldo3OutVol)?;
+        self.write_reg(Register::Ldo3OutVol, (raw_ldo3 & 0x80) | (code & 0x7F))
+    }
+
+    /// Set the LDO4 output voltage
+    ///
+    /// LDO4 shares `Ldo24OutVol` (0x28) with LDO2: LDO2 is the high nibble,
+    /// LDO4 the low nibble. This differs from the 0x27 address used for
+    /// DC-DC3 (see [Register::Dcdc3OutVol]) so the two rails don't alias.
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, snapped to the closest entry in the
+    ///   LDO4 voltage table (1800-3700 mV)
+    pub fn set_ldo4_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = ldo4_mv_to_code(millivolts);
+        let raw_ldo24 = self.read_reg(Register::Ldo24OutVol)?;
+        self.write_reg(Register::Ldo24OutVol, (raw_ldo24 & 0xF0) | (code & 0x0F))
+    }
+
+    /// Set the DC-DC2 output voltage
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 700-2275 mV in 25 mV steps
+    pub fn set_dcdc2_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = linear_mv_to_code(millivolts, 700, 2275, 25);
+        let raw_dcdc2 = self.read_reg(Register::Dcdc2OutVol)?;
+        self.write_reg(Register::Dcdc2OutVol, (raw_dcdc2 & 0xC0) | (code & 0x3F))
+    }
+
+    /// Set the DC-DC3 output voltage
+    ///
+    /// # Arguments
+    ///
+    /// - `millivolts`: target voltage, clamped to 700-3500 mV in 25 mV steps
+    pub fn set_dcdc3_voltage(&mut self, millivolts: u16) -> Result<(), Error<I2C::Error>> {
+        let code = linear_mv_to_code(millivolts, 700, 3500, 25);
+        let raw_dcdc3 = self.read_reg(Register::Dcdc3OutVol)?;
+        self.write_reg(Register::Dcdc3OutVol, (raw_dcdc3 & 0x80) | (code & 0x7F))
+    }
+
+    /// Cut all power rails by setting the shutdown bit
+    pub fn power_off(&mut self) -> Result<(), Error<I2C::Error>> {
+        let raw_shutdown = self.read_reg(Register::PowerOffCtl)?;
+        self.write_reg(Register::PowerOffCtl, raw_shutdown | (1 << 7))
+    }
+
+    /// Read the current power-key (PEK) timing configuration
+    pub fn get_pek_config(&mut self) -> Result<PekConfig, Error<I2C::Error>> {
+        let raw_pek = self.read_reg(Register::PekTiming)?;
+        Ok(PekConfig {
+            startup: PekStartup::from((raw_pek >> 6) & 0b11),
+            long_press: PekLongPress::from((raw_pek >> 4) & 0b11),
+            pwrok_delay: PekPwrokDelay::from((raw_pek >> 2) & 0b1),
+            shutdown_delay: PekShutdownDelay::from(raw_pek & 0b11),
+        })
+    }
+
+    /// Set the power-key (PEK) timing configuration
+    ///
+    /// # Arguments
+    ///
+    /// - `config`: [PekConfig](PekConfig) to apply
+    pub fn set_pek_config(&mut self, config: PekConfig) -> Result<(), Error<I2C::Error>> {
+        let raw_pek = self.read_reg(Register::PekTiming)?;
+        let startup: u8 = config.startup.into();
+        let long_press: u8 = config.long_press.into();
+        let pwrok_delay: u8 = config.pwrok_delay.into();
+        let shutdown_delay: u8 = config.shutdown_delay.into();
+        let raw_pek = (raw_pek & 0b0000_1000)
+            | (startup << 6)
+            | (long_press << 4)
+            | (pwrok_delay << 2)
+            | shutdown_delay;
+        self.write_reg(Register::PekTiming, raw_pek)
+    }
 }